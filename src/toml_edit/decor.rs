@@ -0,0 +1,36 @@
+//! Raw, formatting-preserving building blocks shared by every parsed node.
+
+/// A parsed, already-unescaped TOML string (a key name or similar).
+pub type InternalString = String;
+
+/// Non-semantic whitespace/comments immediately surrounding a node.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Decor {
+    pub prefix: InternalString,
+    pub suffix: InternalString,
+}
+
+/// A node's raw source text, decorated with its surrounding whitespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repr {
+    pub decor: Decor,
+    raw: InternalString,
+}
+
+impl Repr {
+    pub fn new(
+        prefix: impl Into<InternalString>,
+        raw: impl Into<InternalString>,
+        suffix: impl Into<InternalString>,
+    ) -> Self {
+        Repr { decor: Decor { prefix: prefix.into(), suffix: suffix.into() }, raw: raw.into() }
+    }
+
+    pub fn raw(&self) -> &str { &self.raw }
+}
+
+impl std::fmt::Display for Repr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.decor.prefix, self.raw, self.decor.suffix)
+    }
+}
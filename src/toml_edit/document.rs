@@ -0,0 +1,27 @@
+use crate::toml_edit::table::Table;
+
+/// A parsed TOML document, preserving enough of the original formatting to
+/// write it back out byte-for-byte when nothing has changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Document {
+    root: Table,
+    pub trailing: String,
+    /// Whether the source began with a UTF-8 byte-order mark; re-emitted on
+    /// serialization so BOM-prefixed files round-trip instead of silently
+    /// losing their BOM.
+    pub has_bom: bool,
+}
+
+impl Document {
+    pub fn as_table_mut(&mut self) -> &mut Table { &mut self.root }
+}
+
+impl std::fmt::Display for Document {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.has_bom {
+            f.write_str("\u{feff}")?;
+        }
+        self.root.write(f, &[])?;
+        f.write_str(&self.trailing)
+    }
+}
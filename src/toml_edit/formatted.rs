@@ -0,0 +1,8 @@
+use crate::toml_edit::value::Value;
+
+/// Attach the whitespace surrounding a freshly parsed value.
+pub fn decorated(mut value: Value, prefix: impl Into<String>, suffix: impl Into<String>) -> Value {
+    value.decor.prefix = prefix.into();
+    value.decor.suffix = suffix.into();
+    value
+}
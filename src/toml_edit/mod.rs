@@ -0,0 +1,6 @@
+pub mod decor;
+pub mod document;
+pub mod formatted;
+pub mod parser;
+pub mod table;
+pub mod value;
@@ -4,7 +4,7 @@ use combine::{
     char,
     char::char,
     choice, combine_parse_partial, combine_parser_impl, eof, parse_mode, parser,
-    range::recognize,
+    range::{recognize, recognize_with_value},
     skip_many1,
     stream::{state::State, RangeStream},
     ParseError, Parser, StreamOnce,
@@ -23,7 +23,7 @@ use crate::toml_edit::{
         value::value,
         TomlError, TomlParser,
     },
-    table::{Item, TableKeyValue},
+    table::{Item, Table, TableKeyValue},
 };
 
 toml_parser!(parse_comment, parser, {
@@ -40,12 +40,30 @@ toml_parser!(parse_newline, parser, {
 });
 
 toml_parser!(keyval, parser, {
-    parse_keyval().and_then(|(k, kv)| parser.borrow_mut().deref_mut().on_keyval(k, kv))
+    // Wrap the whole keyval in `recognize_with_value` to capture the exact
+    // consumed `&str` alongside its parsed output; diffing that slice's
+    // pointer against the source's gives a real byte range, not just the
+    // stream's line/column `SourcePosition`. The span ends with the value:
+    // the trailing whitespace, comment and line ending are left out.
+    recognize_with_value(parse_keyval()).and_then(|(raw, (path, mut kv))| {
+        let trailing = match &kv.value {
+            Item::Value(v) => v.decor.suffix.len(),
+            _ => 0,
+        };
+        let mut this = parser.borrow_mut();
+        kv.span = Some(this.span_of(&raw[..raw.len() - trailing]));
+        this.deref_mut().on_keyval(path, kv)
+    })
 });
 
 // keyval = key keyval-sep val
+// key = simple-key *( dot-sep simple-key )
+//
+// `key()` yields every dot-separated segment instead of a single name, so
+// `dependencies.serde.version = "1"` and `target.'cfg(unix)'.dependencies.foo = "1"`
+// parse as a path rather than a single (invalid) key containing dots.
 parser! {
-    fn parse_keyval['a, I]()(I) -> (InternalString, TableKeyValue)
+    fn parse_keyval['a, I]()(I) -> (Vec<(InternalString, InternalString)>, TableKeyValue)
     where
         [I: RangeStream<
          Range = &'a str,
@@ -64,30 +82,50 @@ parser! {
         ).map(|(k, _, v)| {
             let (pre, v, suf) = v;
             let v = decorated(v, pre, suf);
-            let ((raw, key), suf) = k;
+            let (segments, suf) = k;
+            // The decor belongs to the *last* segment; the full path (including
+            // that last segment, and each segment's own raw text) is handed to
+            // `on_keyval`, which descends/creates the intermediate tables for
+            // every segment before it, preserving their original (possibly
+            // quoted) spelling.
+            let raw = segments.last().map_or("", |(raw, _)| *raw);
+            let path: Vec<(InternalString, InternalString)> = segments
+                .into_iter()
+                .map(|(raw, key)| (InternalString::from(raw), key))
+                .collect();
             (
-                key,
+                path,
                 TableKeyValue {
                     key: Repr::new("", raw, suf),
                     value: Item::Value(v),
+                    // filled in by the `keyval` wrapper, which has access to the source
+                    span: None,
                 }
             )
         })
     }
 }
 
+// UTF-8 byte-order mark, as prepended by some Windows editors
+const BOM: char = '\u{feff}';
+
 impl TomlParser {
     // ;; TOML
 
-    // toml = expression *( newline expression )
+    // toml = [ BOM ] expression *( newline expression )
 
     // expression = ( ( ws comment ) /
     //                ( ws keyval ws [ comment ] ) /
     //                ( ws table ws [ comment ] ) /
     //                  ws )
     pub fn parse(s: &str) -> Result<Document, TomlError> {
-        let parser = RefCell::new(Self::default());
-        let input = State::new(s);
+        // strip the BOM up front; `document.has_bom` re-emits it on serialization
+        let (has_bom, rest) = match s.strip_prefix(BOM) {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let parser = RefCell::new(Self { source_ptr: rest.as_ptr() as usize, ..Self::default() });
+        let input = State::new(rest);
 
         let parsed = parse_ws(&parser)
             .with(choice((
@@ -104,14 +142,26 @@ impl TomlParser {
             )))
             .easy_parse(input);
         match parsed {
-            Ok((_, ref rest)) if !rest.input.is_empty() => {
-                Err(TomlError::from_unparsed(rest.positioner, s))
+            // top-level grammar matched as far as it could; the rest is unrecognized
+            Ok((_, ref unparsed)) if !unparsed.input.is_empty() => {
+                Err(TomlError::from_unparsed(unparsed.positioner, rest))
+            }
+            Ok(..) => {
+                let mut document = parser.into_inner().document;
+                document.has_bom = has_bom;
+                Ok(*document)
             }
-            Ok(..) => Ok(*parser.into_inner().document),
-            Err(e) => Err(TomlError::new(e, s)),
+            Err(e) => Err(TomlError::new(e, rest)),
         }
     }
 
+    /// Byte range of `raw` (a sub-slice of the source this parser was built
+    /// from) relative to the start of that source.
+    fn span_of(&self, raw: &str) -> std::ops::Range<usize> {
+        let start = raw.as_ptr() as usize - self.source_ptr;
+        start..start + raw.len()
+    }
+
     fn on_ws(&mut self, w: &str) { self.document.trailing.push_str(w); }
 
     fn on_comment(&mut self, c: &str, e: &str) {
@@ -121,24 +171,157 @@ impl TomlParser {
 
     fn on_keyval(
         &mut self,
-        key: InternalString,
+        mut path: Vec<(InternalString, InternalString)>,
         mut kv: TableKeyValue,
     ) -> Result<(), CustomError> {
         let prefix = mem::take(&mut self.document.trailing);
         kv.key.decor.prefix = prefix + &kv.key.decor.prefix;
 
+        let (_, key) = path.pop().expect("a keyval always has at least one key segment");
+
         let root = self.document.as_table_mut();
         let table = Self::descend_path(root, self.current_table_path.as_slice(), 0)
             .expect("the table path is valid; qed");
-        if table.contains_key(&key) {
+        let table = Self::descend_dotted_path(table, &path)?;
+
+        if let Some(original) = table.items.get(&key) {
+            let original_span = original.span.clone();
+            let dotted = path.iter().map(|(_, key)| key.as_str());
             Err(CustomError::DuplicateKey {
                 key,
-                table: "<unknown>".into(), // TODO: get actual table name
+                table: Self::table_path_display(
+                    self.current_table_path.iter().map(InternalString::as_str).chain(dotted),
+                ),
+                original: original_span,
+                duplicate: kv.span,
             })
         } else {
-            let tkv = TableKeyValue { key: kv.key, value: kv.value };
+            let tkv = TableKeyValue { key: kv.key, value: kv.value, span: kv.span };
             table.items.insert(key, tkv);
             Ok(())
         }
     }
+
+    /// Descend through the implicit tables named by a dotted key's non-final
+    /// segments, creating them as we go, e.g. the `dependencies.serde` in
+    /// `dependencies.serde.version = "1"`. Each created table is marked
+    /// `implicit` so it round-trips as dotted keys rather than a `[dependencies.serde]`
+    /// header, and keeps the segment's original (possibly quoted) raw text so
+    /// round-tripping doesn't turn `'cfg(unix)'` into an invalid bare key.
+    /// Colliding with an entry of the same name that is not an implicit table
+    /// (e.g. a prior `[dependencies.serde]` header, or a plain keyval) is a
+    /// conflict rather than something we can silently paper over.
+    fn descend_dotted_path<'t>(
+        mut table: &'t mut Table,
+        segments: &[(InternalString, InternalString)],
+    ) -> Result<&'t mut Table, CustomError> {
+        for (raw, key) in segments {
+            let entry = table.items.entry(key.clone()).or_insert_with(|| TableKeyValue {
+                key: Repr::new("", raw.clone(), ""),
+                value: Item::Table(Table { implicit: true, ..Table::default() }),
+                span: None,
+            });
+            match &mut entry.value {
+                // only an implicit table (itself created by a prior dotted key) can be
+                // descended into further; an explicit `[table]` header is a conflict
+                Item::Table(t) if t.implicit => table = t,
+                _ => return Err(CustomError::DottedKeyConflict { key: key.clone() }),
+            }
+        }
+        Ok(table)
+    }
+
+    /// Render a table path the way a user would write it, e.g.
+    /// `dependencies.serde`, falling back to `<root>` for the document root.
+    fn table_path_display<'p>(path: impl Iterator<Item = &'p str>) -> InternalString {
+        let joined = path.collect::<Vec<_>>().join(".");
+        if joined.is_empty() { "<root>".into() } else { joined }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trailing_garbage_reports_line_and_caret() {
+        let err = TomlParser::parse("a = \"1\"\ngarbage\n").unwrap_err();
+        let rendered = err.to_string();
+        assert!(rendered.contains("line 2"), "{rendered}");
+        assert!(rendered.contains("garbage"), "{rendered}");
+        assert!(rendered.contains('^'), "{rendered}");
+    }
+
+    #[test]
+    fn bom_round_trips() {
+        let src = "\u{feff}a = \"1\"\n";
+        let doc = TomlParser::parse(src).unwrap();
+        assert!(doc.has_bom);
+        assert_eq!(doc.to_string(), src);
+    }
+
+    #[test]
+    fn keyval_span_covers_source_bytes() {
+        let src = "a = \"1\"\n";
+        let mut doc = TomlParser::parse(src).unwrap();
+        let span = doc.as_table_mut().items.get("a").unwrap().span.clone().unwrap();
+        assert_eq!(&src[span], "a = \"1\"");
+    }
+
+    #[test]
+    fn duplicate_key_reports_table_path_and_both_spans() {
+        let mut parser = TomlParser::default();
+        parser.document.as_table_mut().items.insert(
+            "dependencies".into(),
+            TableKeyValue {
+                key: Repr::new("", "dependencies", ""),
+                value: Item::Table(Table::default()),
+                span: None,
+            },
+        );
+        parser.current_table_path = vec!["dependencies".into()];
+        let kv = |span: std::ops::Range<usize>| TableKeyValue {
+            key: Repr::new("", "serde", ""),
+            value: Item::Value(crate::toml_edit::value::Value::new("\"1\"")),
+            span: Some(span),
+        };
+
+        parser.on_keyval(vec![("serde".into(), "serde".into())], kv(0..10)).unwrap();
+        let err = parser
+            .on_keyval(vec![("serde".into(), "serde".into())], kv(20..30))
+            .unwrap_err();
+
+        match err {
+            CustomError::DuplicateKey { key, table, original, duplicate } => {
+                assert_eq!(key, "serde");
+                assert_eq!(table, "dependencies");
+                assert_eq!(original, Some(0..10));
+                assert_eq!(duplicate, Some(20..30));
+            }
+            other => panic!("expected DuplicateKey, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn duplicate_key_error_notes_the_original_definitions_line() {
+        let src = "a = \"1\"\na = \"2\"\n";
+        let err = TomlParser::parse(src).unwrap_err();
+        let rendered = err.to_string();
+        assert!(rendered.contains("line 2"), "{rendered}");
+        assert!(rendered.contains("note: original definition at line 1"), "{rendered}");
+    }
+
+    #[test]
+    fn dotted_key_with_quoted_segment_round_trips() {
+        let src = "target.'cfg(unix)'.dependencies.foo = \"1\"\n";
+        let doc = TomlParser::parse(src).unwrap();
+        assert_eq!(doc.to_string(), src);
+    }
+
+    #[test]
+    fn dotted_key_conflicts_with_explicit_table() {
+        let src = "[dependencies.serde]\nversion = \"1\"\n\n[dependencies]\nserde.version = \"2\"\n";
+        let err = TomlParser::parse(src).unwrap_err();
+        assert!(err.to_string().contains("serde"), "{err}");
+    }
 }
@@ -0,0 +1,34 @@
+use crate::toml_edit::decor::InternalString;
+
+/// Errors raised while building the document from already-parsed grammar
+/// productions, as opposed to grammar-level `TomlError`s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CustomError {
+    /// `key` was assigned twice in `table`; `original`/`duplicate` are the byte
+    /// spans of the two definitions, when parsed from source.
+    DuplicateKey {
+        key: InternalString,
+        table: InternalString,
+        original: Option<std::ops::Range<usize>>,
+        duplicate: Option<std::ops::Range<usize>>,
+    },
+    /// A dotted key or a `[table]` header tried to descend through `key`, but
+    /// `key` already names a plain value or, for a dotted key, an explicit
+    /// `[table]` header rather than an implicit table created for dotted keys.
+    DottedKeyConflict { key: InternalString },
+}
+
+impl std::fmt::Display for CustomError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CustomError::DuplicateKey { key, table, .. } => {
+                write!(f, "duplicate key `{key}` in table `{table}`")
+            }
+            CustomError::DottedKeyConflict { key } => {
+                write!(f, "key `{key}` conflicts with an existing entry of the same name")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CustomError {}
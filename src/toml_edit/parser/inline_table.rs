@@ -0,0 +1,3 @@
+/// The `=` separating a key from its value; pulled out since both the
+/// top-level and (eventually) inline-table grammars share it.
+pub const KEYVAL_SEP: char = '=';
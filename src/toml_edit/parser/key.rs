@@ -0,0 +1,62 @@
+use combine::{
+    attempt,
+    char::char,
+    choice, many, none_of, parser, satisfy, sep_by1,
+    range::recognize,
+    stream::RangeStream,
+    ParseError, Parser, StreamOnce,
+};
+
+use crate::toml_edit::{decor::InternalString, parser::{errors::CustomError, trivia::ws}};
+
+// unquoted-key = 1*( ALPHA / DIGIT / %x2D / %x5F )  ; A-Z / a-z / 0-9 / - / _
+parser! {
+    fn bare_key['a, I]()(I) -> (&'a str, InternalString)
+    where [I: RangeStream<Range = &'a str, Item = char>]
+    {
+        recognize(combine::many1::<Vec<_>, _, _>(
+            satisfy(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        ))
+        .map(|raw: &'a str| (raw, raw.to_string()))
+    }
+}
+
+// quoted-key = basic-string / literal-string
+//
+// Only literal (single-quoted) keys are recognized; basic (double-quoted)
+// keys are not.
+parser! {
+    fn quoted_key['a, I]()(I) -> (&'a str, InternalString)
+    where [I: RangeStream<Range = &'a str, Item = char>]
+    {
+        recognize((char('\''), many::<Vec<_>, _, _>(none_of("'".chars())), char('\'')))
+            .map(|raw: &'a str| (raw, raw[1..raw.len() - 1].to_string()))
+    }
+}
+
+// simple-key = quoted-key / unquoted-key
+parser! {
+    fn simple_key['a, I]()(I) -> (&'a str, InternalString)
+    where [I: RangeStream<Range = &'a str, Item = char>]
+    {
+        choice((quoted_key(), bare_key()))
+    }
+}
+
+// key = simple-key *( dot-sep simple-key )
+//
+// Returns every dot-separated segment (raw text alongside its decoded name) so
+// callers can expand a dotted assignment into nested tables instead of
+// treating the dots as part of a single key.
+parser! {
+    pub fn key['a, I]()(I) -> Vec<(&'a str, InternalString)>
+    where
+        [I: RangeStream<Range = &'a str, Item = char>,
+         I::Error: ParseError<char, &'a str, <I as StreamOnce>::Position>,
+         <I::Error as ParseError<char, &'a str, <I as StreamOnce>::Position>>::StreamError: From<CustomError>]
+    {
+        // `attempt` so the whitespace before a following `=` is not consumed
+        // by a separator that then fails on the missing `.`
+        sep_by1(simple_key(), attempt((ws(), char('.'), ws())))
+    }
+}
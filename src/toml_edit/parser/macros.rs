@@ -0,0 +1,50 @@
+/// Turns a block producing a `Parser` into a standalone parser function that
+/// also has mutable access to the shared `TomlParser` via `$argument`, so a
+/// grammar production can call back into `on_keyval`/`on_table_header`/etc.
+/// as it's recognized.
+macro_rules! toml_parser {
+    ($name:ident, $argument:ident, $code:block) => {
+        #[allow(non_camel_case_types)]
+        pub(crate) struct $name<'a, 's, I>
+        where
+            I: RangeStream<Range = &'a str, Item = char>,
+            I::Error: ParseError<char, &'a str, <I as StreamOnce>::Position>,
+        {
+            $argument: &'s ::std::cell::RefCell<TomlParser>,
+            _marker: ::std::marker::PhantomData<fn(&'a str) -> I>,
+        }
+
+        #[allow(non_snake_case)]
+        pub(crate) fn $name<'a, 's, I>(
+            $argument: &'s ::std::cell::RefCell<TomlParser>,
+        ) -> $name<'a, 's, I>
+        where
+            I: RangeStream<Range = &'a str, Item = char>,
+            I::Error: ParseError<char, &'a str, <I as StreamOnce>::Position>,
+        {
+            $name { $argument, _marker: ::std::marker::PhantomData }
+        }
+
+        impl<'a, 's, I> Parser for $name<'a, 's, I>
+        where
+            I: RangeStream<Range = &'a str, Item = char>,
+            I::Error: ParseError<char, &'a str, <I as StreamOnce>::Position>,
+        {
+            type Input = I;
+            type Output = ();
+            type PartialState = ();
+
+            parse_mode!();
+
+            #[inline]
+            fn parse_partial(
+                &mut self,
+                input: &mut Self::Input,
+                _state: &mut Self::PartialState,
+            ) -> combine::error::ConsumedResult<Self::Output, Self::Input> {
+                let $argument = self.$argument;
+                combine_parse_partial!($code, input)
+            }
+        }
+    };
+}
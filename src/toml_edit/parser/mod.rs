@@ -0,0 +1,129 @@
+use combine::stream::state::SourcePosition;
+
+use crate::toml_edit::{decor::InternalString, document::Document, table::{Item, Table}};
+
+use self::errors::CustomError;
+
+#[macro_use]
+mod macros;
+
+pub mod document;
+pub mod errors;
+mod inline_table;
+mod key;
+mod table;
+mod trivia;
+mod value;
+
+/// Parser state threaded through the `combine` grammar via a `RefCell`; see
+/// `document::parse` for the entry point.
+#[derive(Debug, Default)]
+pub struct TomlParser {
+    document: Box<Document>,
+    current_table_path: Vec<InternalString>,
+    /// Address of the first byte of the source text being parsed, so a
+    /// parsed sub-slice's span can be recovered via pointer arithmetic
+    /// (`document::span_of`). Zero (and unused) outside of `parse()`.
+    source_ptr: usize,
+}
+
+impl TomlParser {
+    /// Walk `path` from `table`, following only existing `[table]` headers.
+    /// `table::table` is responsible for creating headers.
+    fn descend_path<'t>(
+        table: &'t mut Table,
+        path: &[InternalString],
+        idx: usize,
+    ) -> Option<&'t mut Table> {
+        if idx >= path.len() {
+            return Some(table);
+        }
+        match table.items.get_mut(&path[idx]) {
+            Some(kv) => match &mut kv.value {
+                Item::Table(t) => Self::descend_path(t, path, idx + 1),
+                _ => None,
+            },
+            None => None,
+        }
+    }
+}
+
+/// A TOML document failed to parse. `Display` renders a line/column and a
+/// caret pointing at the offending source, e.g.:
+///
+/// ```text
+/// TOML parse error at line 3, column 1: unexpected input; expected a key/value pair, a table header, or end of input
+///   |
+/// 3 | = "oops"
+///   | ^
+/// ```
+///
+/// Errors from the grammar itself carry `combine`'s own description of what
+/// was expected as the message.
+///
+/// For a `DuplicateKey`, `note` additionally points at the original
+/// definition's line, so both conflicting lines are visible.
+#[derive(Debug)]
+pub struct TomlError {
+    message: String,
+    line: usize,
+    column: usize,
+    line_text: String,
+    note: Option<usize>,
+}
+
+impl TomlError {
+    fn at(line: usize, column: usize, source: &str, message: impl Into<String>) -> Self {
+        let line_text = source.lines().nth(line.saturating_sub(1)).unwrap_or("").to_string();
+        TomlError { message: message.into(), line, column, line_text, note: None }
+    }
+
+    /// 1-based line number of byte offset `pos` in `source`.
+    fn line_at(source: &str, pos: usize) -> usize {
+        source[..pos.min(source.len())].matches('\n').count() + 1
+    }
+
+    /// The grammar matched as much as it could, but input remains afterwards.
+    pub fn from_unparsed(pos: SourcePosition, source: &str) -> Self {
+        Self::at(
+            pos.line as usize,
+            pos.column as usize,
+            source,
+            "unexpected input; expected a key/value pair, a table header, or end of input",
+        )
+    }
+
+    /// A `combine` parse failure; `err`'s own `Display` already lists what was
+    /// expected, so reuse it verbatim as the human explanation. If the
+    /// underlying failure was a `DuplicateKey` with a recorded original span,
+    /// also note the original definition's line.
+    pub fn new(err: combine::easy::Errors<char, &str, SourcePosition>, source: &str) -> Self {
+        let pos = err.position;
+        let mut this = Self::at(pos.line as usize, pos.column as usize, source, err.to_string());
+        let original = err.errors.iter().find_map(|e| match e {
+            combine::easy::Error::Other(other) => match other.downcast_ref::<CustomError>() {
+                Some(CustomError::DuplicateKey { original: Some(span), .. }) => Some(span.start),
+                _ => None,
+            },
+            _ => None,
+        });
+        this.note = original.map(|pos| Self::line_at(source, pos));
+        this
+    }
+}
+
+impl std::fmt::Display for TomlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "TOML parse error at line {}, column {}: {}", self.line, self.column, self.message)?;
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.line_text)?;
+        write!(f, "{gutter} | {}^", " ".repeat(self.column.saturating_sub(1)))?;
+        if let Some(note_line) = self.note {
+            write!(f, "\nnote: original definition at line {note_line}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for TomlError {}
@@ -0,0 +1,55 @@
+use std::{mem, ops::DerefMut};
+
+use combine::{
+    char::char, combine_parse_partial, combine_parser_impl, parse_mode, parser,
+    stream::RangeStream, ParseError, Parser, StreamOnce,
+};
+
+use crate::toml_edit::{
+    decor::{InternalString, Repr},
+    parser::{errors::CustomError, key::key, trivia::{line_trailing, ws}, TomlParser},
+    table::{Item, Table, TableKeyValue},
+};
+
+toml_parser!(table, parser, {
+    parse_table_header().and_then(|path| parser.borrow_mut().deref_mut().on_table_header(path))
+});
+
+// std-table = std-table-open key *( table-key-sep key ) std-table-close
+parser! {
+    pub fn parse_table_header['a, I]()(I) -> Vec<InternalString>
+    where
+        [I: RangeStream<Range = &'a str, Item = char>,
+         I::Error: ParseError<char, &'a str, <I as StreamOnce>::Position>,
+         <I::Error as ParseError<char, &'a str, <I as StreamOnce>::Position>>::StreamError: From<CustomError>
+    ] {
+        (char('['), ws(), key(), ws(), char(']'), line_trailing())
+            .map(|(_, _, segments, _, _, _)| segments.into_iter().map(|(_, key)| key).collect())
+    }
+}
+
+impl TomlParser {
+    /// A `[a.b.c]` header: walk/create each segment as an *explicit* table
+    /// (clearing `implicit` on any that a prior dotted key created) and make
+    /// it the table subsequent keyvals are inserted into.
+    fn on_table_header(&mut self, path: Vec<InternalString>) -> Result<(), CustomError> {
+        let prefix = mem::take(&mut self.document.trailing);
+        let mut table = self.document.as_table_mut();
+        for (i, segment) in path.iter().enumerate() {
+            let entry = table.items.entry(segment.clone()).or_insert_with(|| TableKeyValue {
+                key: Repr::new(if i == 0 { prefix.clone() } else { String::new() }, segment.clone(), ""),
+                value: Item::Table(Table::default()),
+                span: None,
+            });
+            match &mut entry.value {
+                Item::Table(t) => {
+                    t.implicit = false;
+                    table = t;
+                }
+                _ => return Err(CustomError::DottedKeyConflict { key: segment.clone() }),
+            }
+        }
+        self.current_table_path = path;
+        Ok(())
+    }
+}
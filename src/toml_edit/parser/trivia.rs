@@ -0,0 +1,56 @@
+use combine::{
+    char::char,
+    choice, many, none_of, optional, parser,
+    range::{recognize, take_while},
+    stream::RangeStream,
+    ParseError, Parser, StreamOnce,
+};
+
+use crate::toml_edit::parser::errors::CustomError;
+
+// ws = *( %x20 / %x09 )  ; space, tab
+parser! {
+    pub fn ws['a, I]()(I) -> &'a str
+    where [I: RangeStream<Range = &'a str, Item = char>]
+    {
+        recognize(take_while(|c: char| c == ' ' || c == '\t'))
+    }
+}
+
+// newline = %x0A / %x0D.0A
+parser! {
+    pub fn newline['a, I]()(I) -> char
+    where [I: RangeStream<Range = &'a str, Item = char>]
+    {
+        choice((char('\n'), (char('\r'), char('\n')).map(|_| '\n')))
+    }
+}
+
+parser! {
+    pub fn line_ending['a, I]()(I) -> &'a str
+    where [I: RangeStream<Range = &'a str, Item = char>]
+    {
+        recognize(optional(newline()))
+    }
+}
+
+// comment-start-symbol *non-eol
+parser! {
+    pub fn comment['a, I]()(I) -> &'a str
+    where [I: RangeStream<Range = &'a str, Item = char>]
+    {
+        recognize((char('#'), many::<Vec<_>, _, _>(none_of("\n\r".chars()))))
+    }
+}
+
+// trailing whitespace, an optional comment, and the line ending that closes it
+parser! {
+    pub fn line_trailing['a, I]()(I) -> &'a str
+    where
+        [I: RangeStream<Range = &'a str, Item = char>,
+         I::Error: ParseError<char, &'a str, <I as StreamOnce>::Position>,
+         <I::Error as ParseError<char, &'a str, <I as StreamOnce>::Position>>::StreamError: From<CustomError>]
+    {
+        recognize((ws(), optional(comment()), line_ending()))
+    }
+}
@@ -0,0 +1,45 @@
+use combine::{
+    char::char,
+    choice, many, many1, none_of, parser, satisfy,
+    range::recognize,
+    stream::RangeStream,
+    ParseError, Parser, StreamOnce,
+};
+
+use crate::toml_edit::{parser::errors::CustomError, value::Value};
+
+// basic-string = quotation-mark *basic-char quotation-mark
+parser! {
+    fn basic_string['a, I]()(I) -> &'a str
+    where [I: RangeStream<Range = &'a str, Item = char>]
+    {
+        recognize((char('"'), many::<Vec<_>, _, _>(none_of("\"".chars())), char('"')))
+    }
+}
+
+// integer / float / boolean / datetime: anything that isn't a string is just
+// a run of non-whitespace, non-comment characters; we keep the raw text
+// verbatim rather than parsing it into a typed representation.
+parser! {
+    fn bare_value['a, I]()(I) -> &'a str
+    where [I: RangeStream<Range = &'a str, Item = char>]
+    {
+        recognize(many1::<Vec<_>, _, _>(satisfy(|c: char| !c.is_whitespace() && c != '#')))
+    }
+}
+
+// val = string / boolean / array / inline-table / date-time / float / integer
+parser! {
+    pub fn value['a, I]()(I) -> Value
+    where
+        [I: RangeStream<Range = &'a str, Item = char>,
+         I::Error: ParseError<char, &'a str, <I as StreamOnce>::Position>,
+         <I::Error as ParseError<char, &'a str, <I as StreamOnce>::Position>>::StreamError:
+         From<std::num::ParseIntError> +
+         From<std::num::ParseFloatError> +
+         From<chrono::ParseError> +
+         From<CustomError>
+    ] {
+        choice((basic_string(), bare_value())).map(Value::new)
+    }
+}
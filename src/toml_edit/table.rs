@@ -0,0 +1,91 @@
+use indexmap::IndexMap;
+
+use crate::toml_edit::{
+    decor::{InternalString, Repr},
+    value::Value,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    None,
+    Value(Value),
+    Table(Table),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableKeyValue {
+    pub key: Repr,
+    pub value: Item,
+    /// Byte range of this keyval in the original source, so a diagnostic can
+    /// slice `&src[span]` to show exactly what was parsed. `None` for
+    /// keyvals built in memory rather than parsed from source text.
+    pub span: Option<std::ops::Range<usize>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Table {
+    pub items: IndexMap<InternalString, TableKeyValue>,
+    /// Set for tables that only exist because a dotted key was assigned through
+    /// them (e.g. the `dependencies.serde` in `dependencies.serde.version = "1"`);
+    /// these render back out as dotted keys rather than a `[dependencies.serde]`
+    /// header.
+    pub implicit: bool,
+}
+
+impl Table {
+    pub fn contains_key(&self, key: &str) -> bool { self.items.contains_key(key) }
+
+    /// Render this table's contents, with `header` as the dotted path of this
+    /// table itself (empty for the document root).
+    pub(crate) fn write(
+        &self,
+        f: &mut impl std::fmt::Write,
+        header: &[InternalString],
+    ) -> std::fmt::Result {
+        self.write_dotted(f, &[])?;
+        for kv in self.items.values() {
+            if let Item::Table(t) = &kv.value {
+                if !t.implicit {
+                    let mut path = header.to_vec();
+                    path.push(kv.key.raw().to_string());
+                    write!(f, "{}[{}]", kv.key.decor.prefix, path.join("."))?;
+                    f.write_str(&kv.key.decor.suffix)?;
+                    t.write(f, &path)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Render the entries of this table (and any implicit dotted sub-tables)
+    /// in place, i.e. without a `[header]` of their own.
+    fn write_dotted(
+        &self,
+        f: &mut impl std::fmt::Write,
+        prefix: &[InternalString],
+    ) -> std::fmt::Result {
+        for kv in self.items.values() {
+            match &kv.value {
+                Item::Value(v) => {
+                    let mut path = prefix.to_vec();
+                    path.push(kv.key.raw().to_string());
+                    write!(
+                        f,
+                        "{}{}{}={}",
+                        kv.key.decor.prefix,
+                        path.join("."),
+                        kv.key.decor.suffix,
+                        v
+                    )?;
+                }
+                Item::Table(t) if t.implicit => {
+                    let mut path = prefix.to_vec();
+                    path.push(kv.key.raw().to_string());
+                    t.write_dotted(f, &path)?;
+                }
+                Item::Table(_) | Item::None => {}
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+//! A parsed value, kept as its raw source text so re-serializing is lossless.
+
+use crate::toml_edit::decor::Decor;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Value {
+    pub decor: Decor,
+    raw: String,
+}
+
+impl Value {
+    pub fn new(raw: impl Into<String>) -> Self {
+        Value { decor: Decor::default(), raw: raw.into() }
+    }
+
+    pub fn raw(&self) -> &str { &self.raw }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.decor.prefix, self.raw, self.decor.suffix)
+    }
+}